@@ -1,21 +1,115 @@
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
-    entrypoint,
-    entrypoint::ProgramResult,
+    entrypoint::{ProgramResult, MAX_PERMITTED_DATA_INCREASE},
     msg,
+    ed25519_program,
+    program_memory::{sol_memcpy, sol_memmove, sol_memset},
     pubkey::Pubkey,
     program_error::ProgramError,
+    secp256k1_program,
+    sysvar::instructions,
 };
 
 /// Команды Write-программы
 #[repr(u8)]
 pub enum WriteCommand {
     Write = 0,
+    /// Как Write, но при необходимости расширяет аккаунт через realloc
+    WriteGrow = 1,
+    /// Заполняет диапазон байт одним значением (memset)
+    Fill = 2,
+    /// Выполняет запись только если в той же транзакции присутствует
+    /// ожидаемая инструкция-компаньон (проверка через instructions sysvar).
+    /// Проверяется только *наличие* precompile-инструкции нужного вида —
+    /// её подписанное сообщение не привязывается к target/payload.
+    WriteIfVerified = 3,
+    /// Копирует байты из второго (source) аккаунта в target_account
+    Copy = 4,
+    /// Применяет несколько сегментов `[offset(4), len(2), bytes...]`
+    /// последовательно к одному аккаунту за одну инструкцию
+    BatchWrite = 5,
     // Можно добавить другие команды для расширения
 }
 
-entrypoint!(process_instruction);
+/// Первые 32 байта данных аккаунта зарезервированы под Pubkey authority
+/// и не видны пользовательским операциям Write/Fill.
+const AUTHORITY_HEADER_LEN: usize = 32;
 
+#[cfg(not(feature = "lean-entrypoint"))]
+solana_program::entrypoint!(process_instruction);
+
+// Облегчённая точка входа: пропускает обёртку, которую генерирует
+// `entrypoint!` (дженерики, дублирующиеся проверки), и напрямую
+// десериализует вход перед вызовом `process_instruction`. Аллокатор и
+// паник-хендлер всё равно нужны — `deserialize` заводит `Vec<AccountInfo>`,
+// а `msg!` форматирует строку, — поэтому эта точка входа по-прежнему
+// регистрирует стандартные `custom_heap_default!`/`custom_panic_default!`,
+// которые иначе даёт `entrypoint!`. Включается фичей `lean-entrypoint`.
+#[cfg(feature = "lean-entrypoint")]
+solana_program::custom_heap_default!();
+#[cfg(feature = "lean-entrypoint")]
+solana_program::custom_panic_default!();
+
+/// # Safety
+/// `input` must point to a valid, runtime-supplied serialized instruction
+/// buffer in the layout expected by `solana_program::entrypoint::deserialize`
+/// — the same contract the runtime upholds for the default `entrypoint!`.
+#[cfg(feature = "lean-entrypoint")]
+#[no_mangle]
+pub unsafe extern "C" fn entrypoint(input: *mut u8) -> u64 {
+    let (program_id, accounts, instruction_data) = solana_program::entrypoint::deserialize(input);
+    match process_instruction(program_id, &accounts, instruction_data) {
+        Ok(()) => solana_program::entrypoint::SUCCESS,
+        Err(error) => error.into(),
+    }
+}
+
+/// Проверяет, что `authority` подписал транзакцию и совпадает с authority,
+/// записанным в заголовке `target_account`. Если заголовок ещё пуст
+/// (все байты нулевые), инициализирует его текущим authority.
+fn check_authority(target_account: &AccountInfo, authority: &AccountInfo) -> ProgramResult {
+    if !authority.is_signer {
+        msg!("Authority must sign the transaction");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if target_account.data_len() < AUTHORITY_HEADER_LEN {
+        msg!("Account too small to hold an authority header");
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+
+    let mut data = target_account.data.borrow_mut();
+    let stored = &mut data[0..AUTHORITY_HEADER_LEN];
+    if stored == Pubkey::default().as_ref() {
+        stored.copy_from_slice(authority.key.as_ref());
+        msg!("Authority initialized to {}", authority.key);
+    } else if stored != authority.key.as_ref() {
+        msg!("Authority does not match the account's stored authority");
+        return Err(ProgramError::IllegalOwner);
+    }
+    Ok(())
+}
+
+/// Читает little-endian u32 прямой индексацией байт вместо
+/// `u32::from_le_bytes`, чтобы не заводить промежуточный `[u8; 4]`.
+fn read_u32_le(data: &[u8]) -> Result<u32, ProgramError> {
+    if data.len() < 4 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    Ok(data[0] as u32
+        | (data[1] as u32) << 8
+        | (data[2] as u32) << 16
+        | (data[3] as u32) << 24)
+}
+
+/// Читает little-endian u16 прямой индексацией байт.
+fn read_u16_le(data: &[u8]) -> Result<u16, ProgramError> {
+    if data.len() < 2 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    Ok(data[0] as u16 | (data[1] as u16) << 8)
+}
+
+#[inline(always)]
 pub fn process_instruction(
     _program_id: &Pubkey,
     accounts: &[AccountInfo],
@@ -23,6 +117,8 @@ pub fn process_instruction(
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
     let target_account = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
+    check_authority(target_account, authority)?;
 
     if instruction_data.len() < 5 {
         msg!("Instruction data too short");
@@ -30,7 +126,7 @@ pub fn process_instruction(
     }
 
     let command = instruction_data[0];
-    let offset = u32::from_le_bytes(instruction_data[1..5].try_into().unwrap()) as usize;
+    let offset = AUTHORITY_HEADER_LEN + read_u32_le(&instruction_data[1..5])? as usize;
     let payload = &instruction_data[5..];
 
     match command {
@@ -42,7 +138,196 @@ pub fn process_instruction(
             }
             let data = &mut target_account.data.borrow_mut();
             data[offset..offset + payload.len()].copy_from_slice(payload);
-            msg!("Write complete at offset {}", offset);
+            msg!("Write complete at offset {}", offset - AUTHORITY_HEADER_LEN);
+            Ok(())
+        }
+        1 => { // WriteGrow
+            let data_len = target_account.data_len();
+            let required_len = offset + payload.len();
+            if required_len > data_len {
+                let increase = required_len - data_len;
+                if increase > MAX_PERMITTED_DATA_INCREASE {
+                    msg!("Realloc of {} bytes exceeds MAX_PERMITTED_DATA_INCREASE", increase);
+                    return Err(ProgramError::AccountDataTooSmall);
+                }
+                target_account
+                    .realloc(required_len, true)
+                    .map_err(|_| ProgramError::InvalidRealloc)?;
+            }
+            let data = &mut target_account.data.borrow_mut();
+            data[offset..offset + payload.len()].copy_from_slice(payload);
+            msg!(
+                "WriteGrow complete at offset {} (new len {})",
+                offset - AUTHORITY_HEADER_LEN,
+                data.len() - AUTHORITY_HEADER_LEN
+            );
+            Ok(())
+        }
+        2 => { // Fill
+            if instruction_data.len() < 10 {
+                msg!("Fill instruction data too short");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+            let len = read_u32_le(&instruction_data[5..9])? as usize;
+            let byte = instruction_data[9];
+            let data_len = target_account.data_len();
+            if offset + len > data_len {
+                msg!("Fill would overflow account data");
+                return Err(ProgramError::InvalidAccountData);
+            }
+            let mut data = target_account.data.borrow_mut();
+            sol_memset(&mut data[offset..offset + len], byte, len);
+            msg!("Filled {} bytes with {} starting at offset {}", len, byte, offset - AUTHORITY_HEADER_LEN);
+            Ok(())
+        }
+        3 => { // WriteIfVerified
+            if instruction_data.len() < 9 {
+                msg!("WriteIfVerified instruction data too short");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+            let sibling_index = read_u16_le(&instruction_data[5..7])?;
+            let self_index = read_u16_le(&instruction_data[7..9])?;
+            let payload = &instruction_data[9..];
+
+            let instructions_account = accounts
+                .last()
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+            if *instructions_account.key != instructions::id() {
+                msg!("Last account is not the instructions sysvar");
+                return Err(ProgramError::InvalidArgument);
+            }
+
+            let current_index = instructions::load_current_index_checked(instructions_account)?;
+            if current_index != self_index {
+                msg!("Self index mismatch: expected {}, got {}", self_index, current_index);
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            let sibling = instructions::load_instruction_at_checked(
+                sibling_index as usize,
+                instructions_account,
+            )?;
+            if sibling.program_id != ed25519_program::id()
+                && sibling.program_id != secp256k1_program::id()
+            {
+                msg!("Sibling instruction is not a recognized precompile");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+            // NOTE: this only proves *some* ed25519/secp256k1 precompile
+            // instruction is present at `sibling_index` in this transaction —
+            // it does not bind that precompile's signed message to this
+            // target account or to `payload`. A caller can satisfy the gate
+            // with any unrelated signature check elsewhere in the same tx.
+            // Callers that need the signed message itself to authorize this
+            // specific write must encode a commitment to the target/payload
+            // (e.g. a hash) inside the precompile's message and verify that
+            // binding on the client before submitting.
+
+            let data_len = target_account.data_len();
+            if offset + payload.len() > data_len {
+                msg!("Write would overflow account data");
+                return Err(ProgramError::InvalidAccountData);
+            }
+            let data = &mut target_account.data.borrow_mut();
+            data[offset..offset + payload.len()].copy_from_slice(payload);
+            msg!("Verified write complete at offset {}", offset - AUTHORITY_HEADER_LEN);
+            Ok(())
+        }
+        4 => { // Copy
+            if instruction_data.len() < 13 {
+                msg!("Copy instruction data too short");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+            let dst_offset = offset;
+            let src_offset = AUTHORITY_HEADER_LEN + read_u32_le(&instruction_data[5..9])? as usize;
+            let len = read_u32_le(&instruction_data[9..13])? as usize;
+
+            let source_account = next_account_info(account_info_iter)?;
+
+            if dst_offset + len > target_account.data_len() {
+                msg!("Copy destination would overflow account data");
+                return Err(ProgramError::InvalidAccountData);
+            }
+            if source_account.data_len() < AUTHORITY_HEADER_LEN {
+                msg!("Source account too small to hold an authority header");
+                return Err(ProgramError::AccountDataTooSmall);
+            }
+            if src_offset + len > source_account.data_len() {
+                msg!("Copy source range exceeds account data");
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            if source_account.key == target_account.key {
+                let mut data = target_account.data.borrow_mut();
+                let base = data.as_mut_ptr();
+                // SAFETY: both ranges are within the bounds-checked single buffer above;
+                // sol_memmove handles overlap correctly, unlike sol_memcpy.
+                unsafe {
+                    sol_memmove(base.add(dst_offset), base.add(src_offset), len);
+                }
+            } else {
+                let mut dst_data = target_account.data.borrow_mut();
+                let src_data = source_account.data.borrow();
+                sol_memcpy(
+                    &mut dst_data[dst_offset..dst_offset + len],
+                    &src_data[src_offset..src_offset + len],
+                    len,
+                );
+            }
+            msg!(
+                "Copy complete: {} bytes from offset {} to offset {}",
+                len,
+                src_offset - AUTHORITY_HEADER_LEN,
+                dst_offset - AUTHORITY_HEADER_LEN
+            );
+            Ok(())
+        }
+        5 => { // BatchWrite
+            let body = &instruction_data[1..];
+            let data_len = target_account.data_len();
+
+            // First pass: validate every segment before touching the account,
+            // so a malformed batch never leaves a partial write behind. No
+            // allocation — just walk the cursor over `body` twice.
+            let mut cursor = 0usize;
+            while cursor < body.len() {
+                if cursor + 6 > body.len() {
+                    msg!("Truncated batch segment header");
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                let seg_offset =
+                    AUTHORITY_HEADER_LEN + read_u32_le(&body[cursor..cursor + 4])? as usize;
+                let seg_len = read_u16_le(&body[cursor + 4..cursor + 6])? as usize;
+                cursor += 6;
+
+                if cursor + seg_len > body.len() {
+                    msg!("Truncated batch segment payload");
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                if seg_offset + seg_len > data_len {
+                    msg!(
+                        "Batch segment at offset {} would overflow account data",
+                        seg_offset - AUTHORITY_HEADER_LEN
+                    );
+                    return Err(ProgramError::InvalidAccountData);
+                }
+                cursor += seg_len;
+            }
+
+            // Second pass: apply, borrowing the account data once for the
+            // whole batch to avoid repeated RefCell churn.
+            let mut data = target_account.data.borrow_mut();
+            let mut cursor = 0usize;
+            while cursor < body.len() {
+                let seg_offset =
+                    AUTHORITY_HEADER_LEN + read_u32_le(&body[cursor..cursor + 4])? as usize;
+                let seg_len = read_u16_le(&body[cursor + 4..cursor + 6])? as usize;
+                cursor += 6;
+                data[seg_offset..seg_offset + seg_len]
+                    .copy_from_slice(&body[cursor..cursor + seg_len]);
+                cursor += seg_len;
+            }
+            msg!("Batch write complete");
             Ok(())
         }
         _ => {
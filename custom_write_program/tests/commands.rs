@@ -0,0 +1,410 @@
+//! Behavior tests for every `WriteCommand` variant, including the
+//! authority-header security path introduced alongside them.
+
+use custom_write_program::process_instruction;
+use solana_program::instruction::InstructionError;
+use solana_program_test::{processor, ProgramTest};
+use solana_sdk::{
+    account::Account,
+    ed25519_instruction::new_ed25519_instruction,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    sysvar,
+    transaction::{Transaction, TransactionError},
+};
+
+const AUTHORITY_HEADER_LEN: usize = 32;
+
+fn target_account(program_id: Pubkey, extra_len: usize) -> Account {
+    Account {
+        lamports: 1_000_000_000,
+        data: vec![0u8; AUTHORITY_HEADER_LEN + extra_len],
+        owner: program_id,
+        ..Account::default()
+    }
+}
+
+fn write_ix_data(cmd: u8, offset: u32, payload: &[u8]) -> Vec<u8> {
+    let mut data = vec![cmd];
+    data.extend_from_slice(&offset.to_le_bytes());
+    data.extend_from_slice(payload);
+    data
+}
+
+async fn setup(program_id: Pubkey, target: Pubkey, data_len: usize) -> ProgramTest {
+    let mut program_test = ProgramTest::new(
+        "custom_write_program",
+        program_id,
+        processor!(process_instruction),
+    );
+    program_test.add_account(target, target_account(program_id, data_len));
+    program_test
+}
+
+#[tokio::test]
+async fn write_grow_reallocates_past_the_original_length() {
+    let program_id = Pubkey::new_unique();
+    let target = Keypair::new();
+    let authority = Keypair::new();
+    let program_test = setup(program_id, target.pubkey(), 4).await;
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let payload = vec![0x42; 64];
+    let instruction = Instruction::new_with_bytes(
+        program_id,
+        &write_ix_data(1, 0, &payload), // WriteGrow
+        vec![
+            AccountMeta::new(target.pubkey(), false),
+            AccountMeta::new_readonly(authority.pubkey(), true),
+        ],
+    );
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &[&payer, &authority],
+        recent_blockhash,
+    );
+    banks_client
+        .process_transaction(transaction)
+        .await
+        .expect("WriteGrow should realloc and succeed");
+
+    let account = banks_client
+        .get_account(target.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(account.data.len(), AUTHORITY_HEADER_LEN + payload.len());
+    assert_eq!(&account.data[AUTHORITY_HEADER_LEN..], payload.as_slice());
+}
+
+#[tokio::test]
+async fn fill_memsets_the_requested_range() {
+    let program_id = Pubkey::new_unique();
+    let target = Keypair::new();
+    let authority = Keypair::new();
+    let program_test = setup(program_id, target.pubkey(), 16).await;
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let mut data = vec![2u8, 0, 0, 0, 0]; // Fill
+    data.extend_from_slice(&4u32.to_le_bytes()); // len = 4
+    data.push(0xFF); // byte
+    let instruction = Instruction::new_with_bytes(
+        program_id,
+        &data,
+        vec![
+            AccountMeta::new(target.pubkey(), false),
+            AccountMeta::new_readonly(authority.pubkey(), true),
+        ],
+    );
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &[&payer, &authority],
+        recent_blockhash,
+    );
+    banks_client
+        .process_transaction(transaction)
+        .await
+        .expect("Fill should succeed");
+
+    let account = banks_client
+        .get_account(target.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        &account.data[AUTHORITY_HEADER_LEN..AUTHORITY_HEADER_LEN + 4],
+        &[0xFF; 4]
+    );
+}
+
+#[tokio::test]
+async fn batch_write_applies_every_segment_in_one_instruction() {
+    let program_id = Pubkey::new_unique();
+    let target = Keypair::new();
+    let authority = Keypair::new();
+    let program_test = setup(program_id, target.pubkey(), 16).await;
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let mut data = vec![5u8]; // BatchWrite
+    data.extend_from_slice(&0u32.to_le_bytes());
+    data.extend_from_slice(&3u16.to_le_bytes());
+    data.extend_from_slice(&[1, 2, 3]);
+    data.extend_from_slice(&8u32.to_le_bytes());
+    data.extend_from_slice(&2u16.to_le_bytes());
+    data.extend_from_slice(&[9, 9]);
+
+    let instruction = Instruction::new_with_bytes(
+        program_id,
+        &data,
+        vec![
+            AccountMeta::new(target.pubkey(), false),
+            AccountMeta::new_readonly(authority.pubkey(), true),
+        ],
+    );
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &[&payer, &authority],
+        recent_blockhash,
+    );
+    banks_client
+        .process_transaction(transaction)
+        .await
+        .expect("BatchWrite should apply both segments");
+
+    let account = banks_client
+        .get_account(target.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        &account.data[AUTHORITY_HEADER_LEN..AUTHORITY_HEADER_LEN + 3],
+        &[1, 2, 3]
+    );
+    assert_eq!(
+        &account.data[AUTHORITY_HEADER_LEN + 8..AUTHORITY_HEADER_LEN + 10],
+        &[9, 9]
+    );
+}
+
+#[tokio::test]
+async fn copy_moves_bytes_between_two_accounts() {
+    let program_id = Pubkey::new_unique();
+    let target = Keypair::new();
+    let source = Keypair::new();
+    let authority = Keypair::new();
+    let mut program_test = setup(program_id, target.pubkey(), 16).await;
+    let mut source_account = target_account(program_id, 16);
+    source_account.data[AUTHORITY_HEADER_LEN..AUTHORITY_HEADER_LEN + 4]
+        .copy_from_slice(&[7, 7, 7, 7]);
+    program_test.add_account(source.pubkey(), source_account);
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let mut data = vec![4u8]; // Copy
+    data.extend_from_slice(&0u32.to_le_bytes()); // dst_offset
+    data.extend_from_slice(&0u32.to_le_bytes()); // src_offset
+    data.extend_from_slice(&4u32.to_le_bytes()); // len
+    let instruction = Instruction::new_with_bytes(
+        program_id,
+        &data,
+        vec![
+            AccountMeta::new(target.pubkey(), false),
+            AccountMeta::new_readonly(authority.pubkey(), true),
+            AccountMeta::new(source.pubkey(), false),
+        ],
+    );
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &[&payer, &authority],
+        recent_blockhash,
+    );
+    banks_client
+        .process_transaction(transaction)
+        .await
+        .expect("Copy should succeed");
+
+    let account = banks_client
+        .get_account(target.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        &account.data[AUTHORITY_HEADER_LEN..AUTHORITY_HEADER_LEN + 4],
+        &[7, 7, 7, 7]
+    );
+}
+
+#[tokio::test]
+async fn copy_handles_overlapping_ranges_within_the_same_account() {
+    let program_id = Pubkey::new_unique();
+    let target = Keypair::new();
+    let authority = Keypair::new();
+    let mut account = target_account(program_id, 16);
+    account.data[AUTHORITY_HEADER_LEN..AUTHORITY_HEADER_LEN + 6]
+        .copy_from_slice(&[1, 2, 3, 4, 5, 6]);
+    let mut program_test = ProgramTest::new(
+        "custom_write_program",
+        program_id,
+        processor!(process_instruction),
+    );
+    program_test.add_account(target.pubkey(), account);
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    // Shift [0..6) to [2..8) — source and destination overlap.
+    let mut data = vec![4u8]; // Copy
+    data.extend_from_slice(&2u32.to_le_bytes()); // dst_offset
+    data.extend_from_slice(&0u32.to_le_bytes()); // src_offset
+    data.extend_from_slice(&6u32.to_le_bytes()); // len
+    let instruction = Instruction::new_with_bytes(
+        program_id,
+        &data,
+        vec![
+            AccountMeta::new(target.pubkey(), false),
+            AccountMeta::new_readonly(authority.pubkey(), true),
+            AccountMeta::new(target.pubkey(), false),
+        ],
+    );
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &[&payer, &authority],
+        recent_blockhash,
+    );
+    banks_client
+        .process_transaction(transaction)
+        .await
+        .expect("overlapping same-account copy should succeed");
+
+    let account = banks_client
+        .get_account(target.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        &account.data[AUTHORITY_HEADER_LEN + 2..AUTHORITY_HEADER_LEN + 8],
+        &[1, 2, 3, 4, 5, 6]
+    );
+}
+
+#[tokio::test]
+async fn write_if_verified_succeeds_with_a_companion_precompile_instruction() {
+    let program_id = Pubkey::new_unique();
+    let target = Keypair::new();
+    let authority = Keypair::new();
+    let signer = Keypair::new();
+    let program_test = setup(program_id, target.pubkey(), 16).await;
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let dalek_keypair = ed25519_dalek::Keypair::from_bytes(&signer.to_bytes()).unwrap();
+    let ed25519_ix = new_ed25519_instruction(&dalek_keypair, b"companion message");
+
+    let payload = [9u8; 4];
+    let mut data = vec![3u8]; // WriteIfVerified
+    data.extend_from_slice(&0u32.to_le_bytes()); // offset
+    data.extend_from_slice(&0u16.to_le_bytes()); // sibling_index (ed25519 ix)
+    data.extend_from_slice(&1u16.to_le_bytes()); // self_index (this ix)
+    data.extend_from_slice(&payload);
+    let write_ix = Instruction::new_with_bytes(
+        program_id,
+        &data,
+        vec![
+            AccountMeta::new(target.pubkey(), false),
+            AccountMeta::new_readonly(authority.pubkey(), true),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    );
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[ed25519_ix, write_ix],
+        Some(&payer.pubkey()),
+        &[&payer, &authority],
+        recent_blockhash,
+    );
+    banks_client
+        .process_transaction(transaction)
+        .await
+        .expect("WriteIfVerified should succeed alongside its companion precompile ix");
+
+    let account = banks_client
+        .get_account(target.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        &account.data[AUTHORITY_HEADER_LEN..AUTHORITY_HEADER_LEN + 4],
+        &payload
+    );
+}
+
+#[tokio::test]
+async fn missing_authority_signature_is_rejected() {
+    let program_id = Pubkey::new_unique();
+    let target = Keypair::new();
+    let authority = Pubkey::new_unique();
+    let program_test = setup(program_id, target.pubkey(), 16).await;
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let instruction = Instruction::new_with_bytes(
+        program_id,
+        &write_ix_data(0, 0, &[1, 2, 3]),
+        vec![
+            AccountMeta::new(target.pubkey(), false),
+            AccountMeta::new_readonly(authority, false), // not a signer
+        ],
+    );
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+    let err = banks_client
+        .process_transaction(transaction)
+        .await
+        .expect_err("write without a signing authority must fail");
+
+    assert_eq!(
+        err.unwrap(),
+        TransactionError::InstructionError(0, InstructionError::MissingRequiredSignature)
+    );
+}
+
+#[tokio::test]
+async fn mismatched_authority_is_rejected_after_first_use() {
+    let program_id = Pubkey::new_unique();
+    let target = Keypair::new();
+    let authority_a = Keypair::new();
+    let authority_b = Keypair::new();
+    let program_test = setup(program_id, target.pubkey(), 16).await;
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    // First write initializes the header to authority_a.
+    let init_ix = Instruction::new_with_bytes(
+        program_id,
+        &write_ix_data(0, 0, &[1, 2, 3]),
+        vec![
+            AccountMeta::new(target.pubkey(), false),
+            AccountMeta::new_readonly(authority_a.pubkey(), true),
+        ],
+    );
+    let init_tx = Transaction::new_signed_with_payer(
+        &[init_ix],
+        Some(&payer.pubkey()),
+        &[&payer, &authority_a],
+        recent_blockhash,
+    );
+    banks_client
+        .process_transaction(init_tx)
+        .await
+        .expect("first write should initialize the authority header");
+
+    // A second write by a different signer must be rejected.
+    let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    let other_ix = Instruction::new_with_bytes(
+        program_id,
+        &write_ix_data(0, 0, &[4, 5, 6]),
+        vec![
+            AccountMeta::new(target.pubkey(), false),
+            AccountMeta::new_readonly(authority_b.pubkey(), true),
+        ],
+    );
+    let other_tx = Transaction::new_signed_with_payer(
+        &[other_ix],
+        Some(&payer.pubkey()),
+        &[&payer, &authority_b],
+        recent_blockhash,
+    );
+    let err = banks_client
+        .process_transaction(other_tx)
+        .await
+        .expect_err("write from a non-matching authority must fail");
+
+    assert_eq!(
+        err.unwrap(),
+        TransactionError::InstructionError(0, InstructionError::IllegalOwner)
+    );
+}
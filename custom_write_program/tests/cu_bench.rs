@@ -0,0 +1,83 @@
+//! Compute-unit micro-benchmark: records the CU cost of a representative
+//! 1 KB `Write` so entrypoint/parsing regressions show up in CI instead of
+//! only being noticed once an on-chain invocation starts running low on CU.
+
+use custom_write_program::process_instruction;
+use solana_program_test::{processor, ProgramTest};
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_program,
+    transaction::Transaction,
+};
+
+/// Upper bound on CU for a 1 KB write, generous enough to absorb routine
+/// compiler/runtime drift but tight enough to flag a real regression.
+const MAX_CU_FOR_1KB_WRITE: u64 = 20_000;
+
+const AUTHORITY_HEADER_LEN: usize = 32;
+const PAYLOAD_LEN: usize = 1024;
+
+#[tokio::test]
+async fn write_1kb_stays_under_cu_budget() {
+    let program_id = Pubkey::new_unique();
+    let mut program_test = ProgramTest::new(
+        "custom_write_program",
+        program_id,
+        processor!(process_instruction),
+    );
+
+    let target = Keypair::new();
+    program_test.add_account(
+        target.pubkey(),
+        Account {
+            lamports: 1_000_000_000,
+            data: vec![0u8; AUTHORITY_HEADER_LEN + PAYLOAD_LEN],
+            owner: program_id,
+            ..Account::default()
+        },
+    );
+
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let mut instruction_data = vec![0u8; 5 + PAYLOAD_LEN];
+    instruction_data[0] = 0; // WriteCommand::Write
+    instruction_data[1..5].copy_from_slice(&0u32.to_le_bytes());
+    instruction_data[5..].copy_from_slice(&[0xAB; PAYLOAD_LEN]);
+
+    let instruction = Instruction::new_with_bytes(
+        program_id,
+        &instruction_data,
+        vec![
+            AccountMeta::new(target.pubkey(), false),
+            AccountMeta::new_readonly(payer.pubkey(), true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    );
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    let result = banks_client
+        .process_transaction_with_metadata(transaction)
+        .await
+        .expect("transaction should be processed");
+    result.result.expect("write instruction should succeed");
+
+    let compute_units_consumed = result
+        .metadata
+        .expect("simulation metadata should be present")
+        .compute_units_consumed;
+    assert!(
+        compute_units_consumed <= MAX_CU_FOR_1KB_WRITE,
+        "1 KB write regressed: {} CU > budget of {} CU",
+        compute_units_consumed,
+        MAX_CU_FOR_1KB_WRITE,
+    );
+}